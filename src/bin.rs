@@ -49,7 +49,7 @@ fn formatted_logger() -> Builder {
     builder
 }
 
-fn colored_level(level: Level) -> (Style, &'static str) {
+pub(crate) fn colored_level(level: Level) -> (Style, &'static str) {
     let (color, text) = match level {
         Level::Trace => (AnsiColor::Magenta, "TRACE"),
         Level::Debug => (AnsiColor::Blue, "DEBUG"),
@@ -0,0 +1,117 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Parser;
+use log::{Level, LevelFilter};
+
+use darklua::configuration::{Configuration, ConfigurationError};
+use darklua::lua_generator::ToLua;
+use darklua::nodes::Block;
+use darklua::rules::{get_default_rules, Diagnostic, Rule, RuleContext, Severity};
+
+/// Transforms a Lua file by running it through a pipeline of rules.
+#[derive(Debug, Parser)]
+#[command(name = "darklua")]
+pub struct Darklua {
+    /// The Lua file to process.
+    input: PathBuf,
+    /// Where to write the transformed code. Defaults to overwriting the input file.
+    output: Option<PathBuf>,
+    /// A configuration file (`.json`, `.yml` or `.toml`) selecting and ordering the rules to run.
+    /// Defaults to running [`get_default_rules`].
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Report what the rules would do without writing any file.
+    #[arg(long)]
+    dry_run: bool,
+    /// Increase logging verbosity. Can be repeated (-v, -vv, -vvv).
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Debug)]
+pub enum DarkluaError {
+    Io(PathBuf, String),
+    Parse(PathBuf, String),
+    Configuration(ConfigurationError),
+}
+
+impl fmt::Display for DarkluaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, message) => write!(f, "unable to read or write '{}': {}", path.display(), message),
+            Self::Parse(path, message) => write!(f, "unable to parse '{}': {}", path.display(), message),
+            Self::Configuration(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl DarkluaError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Io(..) => 1,
+            Self::Parse(..) => 2,
+            Self::Configuration(..) => 3,
+        }
+    }
+}
+
+impl Darklua {
+    pub fn get_log_level_filter(&self) -> LevelFilter {
+        match self.verbose {
+            0 => LevelFilter::Info,
+            1 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+
+    pub fn run(&self) -> Result<(), DarkluaError> {
+        let rules = self.get_rules()?;
+
+        let content = fs::read_to_string(&self.input)
+            .map_err(|err| DarkluaError::Io(self.input.clone(), err.to_string()))?;
+
+        let mut block = Block::parse(&content)
+            .map_err(|err| DarkluaError::Parse(self.input.clone(), err.to_string()))?;
+
+        for rule in &rules {
+            let mut context = RuleContext::new();
+            rule.process(&mut block, &mut context);
+
+            for diagnostic in context.get_diagnostics() {
+                self.log_diagnostic(rule.get_name(), diagnostic);
+            }
+        }
+
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let output_path = self.output.clone().unwrap_or_else(|| self.input.clone());
+
+        fs::write(&output_path, block.to_lua_string())
+            .map_err(|err| DarkluaError::Io(output_path, err.to_string()))
+    }
+
+    fn get_rules(&self) -> Result<Vec<Box<dyn Rule>>, DarkluaError> {
+        match &self.config {
+            Some(path) => Configuration::from_file(path)
+                .map(Configuration::into_rules)
+                .map_err(DarkluaError::Configuration),
+            None => Ok(get_default_rules()),
+        }
+    }
+
+    /// Routes a rule's diagnostic through the `log` crate, so it is rendered by the same
+    /// colored, leveled formatter the rest of the CLI's output goes through.
+    fn log_diagnostic(&self, rule_name: &str, diagnostic: &Diagnostic) {
+        let level = match diagnostic.get_severity() {
+            Severity::Info => Level::Info,
+            Severity::Warning => Level::Warn,
+            Severity::Error => Level::Error,
+        };
+
+        log::log!(level, "[{}] {}", rule_name, diagnostic.get_message());
+    }
+}
@@ -0,0 +1,25 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// An error produced while loading a [`super::Configuration`] from disk.
+#[derive(Debug, Clone)]
+pub enum ConfigurationError {
+    Io(PathBuf, String),
+    /// The configuration file extension is not one of `.json`, `.yml`, `.yaml` or `.toml`.
+    UnsupportedExtension(PathBuf),
+    Parse(PathBuf, String),
+}
+
+impl fmt::Display for ConfigurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, message) => write!(f, "unable to read '{}': {}", path.display(), message),
+            Self::UnsupportedExtension(path) => write!(
+                f,
+                "unsupported configuration file extension for '{}' (expected .json, .yml, .yaml or .toml)",
+                path.display()
+            ),
+            Self::Parse(path, message) => write!(f, "unable to parse '{}': {}", path.display(), message),
+        }
+    }
+}
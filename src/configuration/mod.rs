@@ -0,0 +1,136 @@
+//! Loads a full rule pipeline from a user-provided configuration file (`.darklua.json`,
+//! `.darklua.yml` or `.darklua.toml`), so a project can select and order its own rules instead of
+//! relying on [`crate::rules::get_default_rules`].
+
+mod error;
+
+pub use error::ConfigurationError;
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rules::Rule;
+
+/// Options that apply to the whole pipeline, rather than to a single rule.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GlobalOptions {
+    /// The name of a Lua code generator (for example `"dense"` or `"readable"`). It is only
+    /// stored: the CLI always writes its output with the default generator.
+    #[serde(default)]
+    pub generator: Option<String>,
+}
+
+/// A full, ordered rule pipeline, as loaded from a configuration file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Configuration {
+    #[serde(flatten)]
+    pub global: GlobalOptions,
+    #[serde(default)]
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Configuration {
+    /// Loads a configuration from a JSON, YAML or TOML file, picked based on its extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigurationError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|err| ConfigurationError::Io(path.to_owned(), err.to_string()))?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => serde_json::from_str(&content)
+                .map_err(|err| ConfigurationError::Parse(path.to_owned(), err.to_string())),
+            Some("yml") | Some("yaml") => serde_yaml::from_str(&content)
+                .map_err(|err| ConfigurationError::Parse(path.to_owned(), err.to_string())),
+            Some("toml") => toml::from_str(&content)
+                .map_err(|err| ConfigurationError::Parse(path.to_owned(), err.to_string())),
+            _ => Err(ConfigurationError::UnsupportedExtension(path.to_owned())),
+        }
+    }
+
+    pub fn get_rules(&self) -> &[Box<dyn Rule>] {
+        &self.rules
+    }
+
+    pub fn into_rules(self) -> Vec<Box<dyn Rule>> {
+        self.rules
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn write_fixture(extension: &str, content: &str) -> std::path::PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock should be after the epoch")
+            .as_nanos();
+
+        let path = std::env::temp_dir().join(format!("darklua-configuration-test-{}.{}", nanos, extension));
+        fs::write(&path, content).expect("should be able to write to the temp directory");
+
+        path
+    }
+
+    #[test]
+    fn loads_toml_configuration_with_bare_string_rules() {
+        let path = write_fixture(
+            "toml",
+            r#"
+            rules = ["fold_constants", "remove_unused_locals"]
+            "#,
+        );
+
+        let configuration = Configuration::from_file(&path).expect("should parse");
+
+        assert_eq!(configuration.get_rules().len(), 2);
+    }
+
+    #[test]
+    fn loads_yaml_configuration_with_property_table_rules() {
+        let path = write_fixture(
+            "yml",
+            "rules:\n  - rule: rename_variables\n    preserve:\n      - keep_me\n",
+        );
+
+        let configuration = Configuration::from_file(&path).expect("should parse");
+
+        assert_eq!(configuration.get_rules().len(), 1);
+        assert_eq!(configuration.get_rules()[0].get_name(), "rename_variables");
+    }
+
+    #[test]
+    fn loads_json_configuration_with_property_table_rules() {
+        let path = write_fixture(
+            "json",
+            r#"{"rules": [{"rule": "rename_variables", "preserve": ["keep_me"]}]}"#,
+        );
+
+        let configuration = Configuration::from_file(&path).expect("should parse");
+
+        assert_eq!(configuration.get_rules().len(), 1);
+        assert_eq!(configuration.get_rules()[0].get_name(), "rename_variables");
+    }
+
+    #[test]
+    fn rejects_unsupported_extension() {
+        let path = write_fixture("ini", "rules = []");
+
+        let error = Configuration::from_file(&path).unwrap_err();
+
+        assert!(matches!(error, ConfigurationError::UnsupportedExtension(_)));
+    }
+
+    #[test]
+    fn rejects_unknown_rule_name() {
+        let path = write_fixture("toml", r#"rules = ["not_a_real_rule"]"#);
+
+        let error = Configuration::from_file(&path).unwrap_err();
+
+        assert!(matches!(error, ConfigurationError::Parse(_, _)));
+    }
+}
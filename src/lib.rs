@@ -0,0 +1,9 @@
+//! The darklua library: parses Lua code into an AST, applies a configurable pipeline of rules to
+//! it, and regenerates Lua source from the result.
+
+pub mod configuration;
+pub mod lua_generator;
+pub mod nodes;
+pub mod process;
+pub mod rules;
+pub mod standard_library;
@@ -0,0 +1,10 @@
+//! A module that contains generic helpers to walk and mutate the Lua abstract syntax tree.
+//!
+//! Rules that need to analyze more than a single node (for example to track identifier scopes)
+//! can implement [`NodeProcessor`] and drive it over a block with [`ScopeVisitor`].
+
+mod node_processor;
+mod scope_visitor;
+
+pub use node_processor::NodeProcessor;
+pub use scope_visitor::ScopeVisitor;
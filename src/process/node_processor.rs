@@ -0,0 +1,36 @@
+use crate::nodes::{
+    Block, Expression, FunctionExpression, GenericForStatement, LocalAssignStatement,
+    LocalFunctionStatement, NumericForStatement,
+};
+
+/// A trait with a method for each kind of node that introduces or refers to an identifier, used
+/// by [`super::ScopeVisitor`] to drive scope-aware passes (renaming, dead code elimination, ...).
+///
+/// Every method has a default empty implementation, so implementors only need to override the
+/// ones relevant to their pass.
+pub trait NodeProcessor {
+    /// Called once for every block, before its statements are processed. Blocks introduce a new
+    /// scope, so this is the place to push a new scope onto a scope stack.
+    fn process_scope_start(&mut self, _block: &mut Block) {}
+    /// Called once for every block, after its statements have been processed. This is the
+    /// counterpart of [`Self::process_scope_start`] and should pop the scope it pushed.
+    fn process_scope_end(&mut self) {}
+
+    /// Called for every `local` declaration, after its values have been visited, so a `local x =
+    /// x` initializer still resolves `x` to the outer scope rather than to the binding it creates.
+    fn process_local_assign_statement(&mut self, _statement: &mut LocalAssignStatement) {}
+    /// Called for every `local function` declaration.
+    fn process_local_function_statement(&mut self, _statement: &mut LocalFunctionStatement) {}
+    /// Called for every numeric `for` statement, after its range expressions and before its body,
+    /// so the loop control variable can be bound for the body's scope.
+    fn process_numeric_for_statement(&mut self, _statement: &mut NumericForStatement) {}
+    /// Called for every generic `for .. in` statement, after its expressions and before its body,
+    /// so the loop variables can be bound for the body's scope.
+    fn process_generic_for_statement(&mut self, _statement: &mut GenericForStatement) {}
+    /// Called for every function expression, before its body, so its parameters can be bound for
+    /// the body's scope.
+    fn process_function_expression(&mut self, _function: &mut FunctionExpression) {}
+
+    /// Called for every expression, used to observe and rewrite identifier references.
+    fn process_expression(&mut self, _expression: &mut Expression) {}
+}
@@ -0,0 +1,156 @@
+use crate::nodes::{Block, Expression, LastStatement, Statement};
+
+use super::NodeProcessor;
+
+/// Walks a [`Block`] and every nested block, statement and expression inside of it, notifying a
+/// [`NodeProcessor`] along the way. Scopes are entered and left in order, so a processor tracking
+/// a scope stack only ever sees bindings that are currently visible.
+pub struct ScopeVisitor;
+
+impl ScopeVisitor {
+    pub fn visit_block<T: NodeProcessor>(block: &mut Block, processor: &mut T) {
+        processor.process_scope_start(block);
+
+        for statement in block.mutate_statements() {
+            Self::visit_statement(statement, processor);
+        }
+
+        if let Some(last_statement) = block.mutate_last_statement() {
+            Self::visit_last_statement(last_statement, processor);
+        }
+
+        processor.process_scope_end();
+    }
+
+    fn visit_statement<T: NodeProcessor>(statement: &mut Statement, processor: &mut T) {
+        match statement {
+            Statement::Assign(assign) => {
+                for value in assign.mutate_values() {
+                    Self::visit_expression(value, processor);
+                }
+                // An assignment target can itself reference other variables (e.g. the `t` in
+                // `t.x = 1`, or the index in `t[i] = 1`), so it must be visited too.
+                for variable in assign.mutate_variables() {
+                    Self::visit_expression(variable, processor);
+                }
+            }
+            Statement::Do(do_statement) => {
+                Self::visit_block(do_statement.mutate_block(), processor);
+            }
+            Statement::LocalAssign(local_assign) => {
+                for value in local_assign.mutate_values() {
+                    Self::visit_expression(value, processor);
+                }
+                processor.process_local_assign_statement(local_assign);
+            }
+            Statement::LocalFunction(local_function) => {
+                processor.process_local_function_statement(local_function);
+                Self::visit_block(local_function.mutate_block(), processor);
+            }
+            Statement::If(if_statement) => {
+                for branch in if_statement.mutate_branches() {
+                    Self::visit_expression(branch.mutate_condition(), processor);
+                    Self::visit_block(branch.mutate_block(), processor);
+                }
+                if let Some(block) = if_statement.mutate_else_block() {
+                    Self::visit_block(block, processor);
+                }
+            }
+            Statement::While(while_statement) => {
+                Self::visit_expression(while_statement.mutate_condition(), processor);
+                Self::visit_block(while_statement.mutate_block(), processor);
+            }
+            Statement::Repeat(repeat_statement) => {
+                // The `until` condition can see the locals declared in the loop body, so it is
+                // visited before the body's scope is closed.
+                processor.process_scope_start(repeat_statement.mutate_block());
+                for statement in repeat_statement.mutate_block().mutate_statements() {
+                    Self::visit_statement(statement, processor);
+                }
+                if let Some(last_statement) = repeat_statement.mutate_block().mutate_last_statement() {
+                    Self::visit_last_statement(last_statement, processor);
+                }
+                Self::visit_expression(repeat_statement.mutate_condition(), processor);
+                processor.process_scope_end();
+            }
+            Statement::NumericFor(numeric_for) => {
+                Self::visit_expression(numeric_for.mutate_start(), processor);
+                Self::visit_expression(numeric_for.mutate_end(), processor);
+                if let Some(step) = numeric_for.mutate_step() {
+                    Self::visit_expression(step, processor);
+                }
+                processor.process_numeric_for_statement(numeric_for);
+                Self::visit_block(numeric_for.mutate_block(), processor);
+            }
+            Statement::GenericFor(generic_for) => {
+                for value in generic_for.mutate_expressions() {
+                    Self::visit_expression(value, processor);
+                }
+                processor.process_generic_for_statement(generic_for);
+                Self::visit_block(generic_for.mutate_block(), processor);
+            }
+            Statement::Function(function_statement) => {
+                // The name being assigned to (e.g. the `t.foo` in `function t.foo() end`) can
+                // reference other variables, just like any other assignment target.
+                Self::visit_expression(function_statement.mutate_name(), processor);
+                Self::visit_expression(function_statement.mutate_function(), processor);
+            }
+            Statement::CompoundAssign(compound_assign) => {
+                Self::visit_expression(compound_assign.mutate_variable(), processor);
+                Self::visit_expression(compound_assign.mutate_value(), processor);
+            }
+            Statement::Call(call) => {
+                for argument in call.mutate_arguments() {
+                    Self::visit_expression(argument, processor);
+                }
+            }
+        }
+    }
+
+    fn visit_last_statement<T: NodeProcessor>(last_statement: &mut LastStatement, processor: &mut T) {
+        if let LastStatement::Return(expressions) = last_statement {
+            for expression in expressions {
+                Self::visit_expression(expression, processor);
+            }
+        }
+    }
+
+    fn visit_expression<T: NodeProcessor>(expression: &mut Expression, processor: &mut T) {
+        match expression {
+            Expression::Binary(binary) => {
+                Self::visit_expression(binary.mutate_left(), processor);
+                Self::visit_expression(binary.mutate_right(), processor);
+            }
+            Expression::Unary(unary) => {
+                Self::visit_expression(unary.mutate_expression(), processor);
+            }
+            Expression::Parenthese(inner) => {
+                Self::visit_expression(inner, processor);
+            }
+            Expression::Function(function) => {
+                processor.process_function_expression(function);
+                Self::visit_block(function.mutate_block(), processor);
+            }
+            Expression::Table(table) => {
+                for entry in table.mutate_entries() {
+                    Self::visit_expression(entry, processor);
+                }
+            }
+            Expression::Call(call) => {
+                for argument in call.mutate_arguments() {
+                    Self::visit_expression(argument, processor);
+                }
+            }
+            Expression::Field(field) => {
+                Self::visit_expression(field.mutate_object(), processor);
+            }
+            Expression::Index(index) => {
+                Self::visit_expression(index.mutate_object(), processor);
+                Self::visit_expression(index.mutate_index(), processor);
+            }
+            _ => {}
+        }
+
+        processor.process_expression(expression);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::nodes::Expression;
+
+/// Tries to evaluate a call to a known pure library function whose arguments are all literals.
+/// Only a handful of common functions are supported: this rule would rather leave a call alone
+/// than guess at Lua semantics it does not fully model.
+pub fn evaluate_pure_call(path: &[String], arguments: &[Expression]) -> Option<Expression> {
+    let numbers: Option<Vec<f64>> = arguments
+        .iter()
+        .map(|argument| match argument {
+            Expression::Number(value) => Some(*value),
+            _ => None,
+        })
+        .collect();
+
+    match path {
+        [first, second] if first == "math" => {
+            let numbers = numbers?;
+            let value = match second.as_str() {
+                "floor" => numbers.first()?.floor(),
+                "ceil" => numbers.first()?.ceil(),
+                "abs" => numbers.first()?.abs(),
+                "sqrt" => numbers.first()?.sqrt(),
+                "max" => numbers.into_iter().reduce(f64::max)?,
+                "min" => numbers.into_iter().reduce(f64::min)?,
+                _ => return None,
+            };
+
+            Some(Expression::Number(value))
+        }
+        [first, second] if first == "string" && second == "len" => {
+            if let [Expression::String(value)] = arguments {
+                Some(Expression::Number(value.len() as f64))
+            } else {
+                None
+            }
+        }
+        [first, second] if first == "string" && second == "upper" => {
+            if let [Expression::String(value)] = arguments {
+                // Lua's `string.upper` only cases ASCII bytes under the default "C" locale, unlike
+                // Rust's Unicode-aware `str::to_uppercase`, which would also case non-ASCII bytes
+                // and change the program's output.
+                Some(Expression::String(value.to_ascii_uppercase()))
+            } else {
+                None
+            }
+        }
+        [first, second] if first == "string" && second == "lower" => {
+            if let [Expression::String(value)] = arguments {
+                Some(Expression::String(value.to_ascii_lowercase()))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn folds_math_floor() {
+        let result = evaluate_pure_call(
+            &["math".to_owned(), "floor".to_owned()],
+            &[Expression::Number(1.5)],
+        );
+
+        assert_eq!(result, Some(Expression::Number(1.0)));
+    }
+
+    #[test]
+    fn string_upper_only_cases_ascii_bytes() {
+        let result = evaluate_pure_call(
+            &["string".to_owned(), "upper".to_owned()],
+            &[Expression::String("café".to_owned())],
+        );
+
+        assert_eq!(result, Some(Expression::String("CAFé".to_owned())));
+    }
+
+    #[test]
+    fn string_lower_only_cases_ascii_bytes() {
+        let result = evaluate_pure_call(
+            &["string".to_owned(), "lower".to_owned()],
+            &[Expression::String("CAFÉ".to_owned())],
+        );
+
+        assert_eq!(result, Some(Expression::String("cafÉ".to_owned())));
+    }
+}
@@ -0,0 +1,305 @@
+//! Evaluates expressions made only of literals and pure library calls at transform time.
+
+mod evaluator;
+
+use crate::nodes::{BinaryOperator, Block, Expression, UnaryOperator};
+use crate::process::{NodeProcessor, ScopeVisitor};
+use crate::standard_library::StandardLibrary;
+
+use self::evaluator::evaluate_pure_call;
+
+use super::purity::{dotted_path, is_side_effect_free};
+use super::{
+    Diagnostic, Rule, RuleConfigurationError, RuleContext, RuleProperties, RulePropertyValue,
+};
+
+pub const FOLD_CONSTANTS_RULE_NAME: &str = "fold_constants";
+
+/// A rule that replaces expressions it can fully evaluate (constant arithmetic, string
+/// concatenation, and calls to known-pure library functions with literal arguments) with their
+/// result.
+#[derive(Debug)]
+pub struct FoldConstants {
+    standard_library: StandardLibrary,
+    /// The raw `standard_library` property value this rule was configured with (a built-in name
+    /// or a file path), kept only so `serialize_to_properties` can round-trip it: a resolved
+    /// `StandardLibrary` no longer carries the selector it was built from.
+    standard_library_property: Option<String>,
+}
+
+impl Default for FoldConstants {
+    fn default() -> Self {
+        Self {
+            standard_library: StandardLibrary::lua51(),
+            standard_library_property: None,
+        }
+    }
+}
+
+impl FoldConstants {
+    pub fn with_standard_library(mut self, standard_library: StandardLibrary) -> Self {
+        self.standard_library = standard_library;
+        self
+    }
+}
+
+impl Rule for FoldConstants {
+    fn process(&self, block: &mut Block, context: &mut RuleContext) {
+        let mut folder = ConstantFolder {
+            standard_library: &self.standard_library,
+            folded_count: 0,
+        };
+
+        ScopeVisitor::visit_block(block, &mut folder);
+
+        for _ in 0..folder.folded_count {
+            context.record_mutation();
+        }
+
+        if folder.folded_count > 0 {
+            context.report(Diagnostic::info(format!(
+                "folded {} constant expression(s)",
+                folder.folded_count
+            )));
+        }
+    }
+
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "standard_library" => match value {
+                    RulePropertyValue::String(name) => {
+                        self.standard_library = StandardLibrary::from_name_or_file(&name)
+                            .map_err(|err| {
+                                RuleConfigurationError::StandardLibraryExpected(
+                                    key.clone(),
+                                    err.to_string(),
+                                )
+                            })?;
+                        self.standard_library_property = Some(name);
+                    }
+                    _ => return Err(RuleConfigurationError::StringExpected(key)),
+                },
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_CONSTANTS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if let Some(name) = &self.standard_library_property {
+            properties.insert(
+                "standard_library".to_owned(),
+                RulePropertyValue::String(name.clone()),
+            );
+        }
+
+        properties
+    }
+}
+
+struct ConstantFolder<'a> {
+    standard_library: &'a StandardLibrary,
+    folded_count: usize,
+}
+
+impl<'a> NodeProcessor for ConstantFolder<'a> {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Some(folded) = self.fold(expression) {
+            *expression = folded;
+            self.folded_count += 1;
+        }
+    }
+}
+
+impl<'a> ConstantFolder<'a> {
+    fn fold(&self, expression: &Expression) -> Option<Expression> {
+        match expression {
+            Expression::Binary(binary) => {
+                fold_binary(binary.get_operator(), binary.get_left(), binary.get_right())
+            }
+            Expression::Unary(unary) => fold_unary(unary.get_operator(), unary.get_expression()),
+            Expression::Parenthese(inner) => match inner.as_ref() {
+                Expression::Number(_)
+                | Expression::String(_)
+                | Expression::True
+                | Expression::False
+                | Expression::Nil => Some(inner.as_ref().clone()),
+                _ => None,
+            },
+            Expression::Call(call) => {
+                let path = dotted_path(call.get_prefix())?;
+
+                if !self.standard_library.is_pure(&path) {
+                    return None;
+                }
+
+                let arguments = call.get_arguments();
+
+                if !arguments
+                    .iter()
+                    .all(|argument| is_side_effect_free(argument, self.standard_library))
+                {
+                    return None;
+                }
+
+                evaluate_pure_call(&path, arguments)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn fold_binary(
+    operator: BinaryOperator,
+    left: &Expression,
+    right: &Expression,
+) -> Option<Expression> {
+    use BinaryOperator::*;
+
+    match (operator, left, right) {
+        // A non-finite result (e.g. `1/0`, `0/0`, or an overflowing `1e308 * 10`) is left
+        // unfolded: whether it stays representable once handed to the code generator isn't
+        // something this rule can guarantee, so it's safer to leave the original expression for
+        // Lua itself to evaluate at runtime.
+        (Plus, Expression::Number(a), Expression::Number(b)) => finite_number(a + b),
+        (Minus, Expression::Number(a), Expression::Number(b)) => finite_number(a - b),
+        (Asterisk, Expression::Number(a), Expression::Number(b)) => finite_number(a * b),
+        (Slash, Expression::Number(a), Expression::Number(b)) => finite_number(a / b),
+        (Percent, Expression::Number(a), Expression::Number(b)) => {
+            finite_number(a - (a / b).floor() * b)
+        }
+        (Caret, Expression::Number(a), Expression::Number(b)) => finite_number(a.powf(*b)),
+        (Concat, Expression::String(a), Expression::String(b)) => {
+            Some(Expression::String(format!("{}{}", a, b)))
+        }
+        (Equal, Expression::Number(a), Expression::Number(b)) => Some(bool_expression(a == b)),
+        (NotEqual, Expression::Number(a), Expression::Number(b)) => Some(bool_expression(a != b)),
+        (LowerThan, Expression::Number(a), Expression::Number(b)) => Some(bool_expression(a < b)),
+        (LowerOrEqualThan, Expression::Number(a), Expression::Number(b)) => {
+            Some(bool_expression(a <= b))
+        }
+        (GreaterThan, Expression::Number(a), Expression::Number(b)) => Some(bool_expression(a > b)),
+        (GreaterOrEqualThan, Expression::Number(a), Expression::Number(b)) => {
+            Some(bool_expression(a >= b))
+        }
+        _ => None,
+    }
+}
+
+fn fold_unary(operator: UnaryOperator, operand: &Expression) -> Option<Expression> {
+    match (operator, operand) {
+        (UnaryOperator::Minus, Expression::Number(value)) => Some(Expression::Number(-value)),
+        (UnaryOperator::Not, Expression::True) => Some(Expression::False),
+        (UnaryOperator::Not, Expression::False) => Some(Expression::True),
+        (UnaryOperator::Not, Expression::Nil) => Some(Expression::True),
+        (UnaryOperator::Length, Expression::String(value)) => {
+            Some(Expression::Number(value.len() as f64))
+        }
+        _ => None,
+    }
+}
+
+fn bool_expression(value: bool) -> Expression {
+    if value {
+        Expression::True
+    } else {
+        Expression::False
+    }
+}
+
+fn finite_number(value: f64) -> Option<Expression> {
+    value.is_finite().then_some(Expression::Number(value))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn folds_finite_division() {
+        let result = fold_binary(BinaryOperator::Slash, &Expression::Number(4.0), &Expression::Number(2.0));
+
+        assert_eq!(result, Some(Expression::Number(2.0)));
+    }
+
+    #[test]
+    fn does_not_fold_division_by_zero() {
+        let result = fold_binary(BinaryOperator::Slash, &Expression::Number(1.0), &Expression::Number(0.0));
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn does_not_fold_zero_divided_by_zero() {
+        let result = fold_binary(BinaryOperator::Slash, &Expression::Number(0.0), &Expression::Number(0.0));
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn does_not_fold_non_finite_power() {
+        let result = fold_binary(BinaryOperator::Caret, &Expression::Number(0.0), &Expression::Number(-1.0));
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn does_not_fold_overflowing_multiplication() {
+        let result = fold_binary(
+            BinaryOperator::Asterisk,
+            &Expression::Number(1e308),
+            &Expression::Number(10.0),
+        );
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn folds_finite_addition() {
+        let result = fold_binary(BinaryOperator::Plus, &Expression::Number(1.0), &Expression::Number(2.0));
+
+        assert_eq!(result, Some(Expression::Number(3.0)));
+    }
+
+    #[test]
+    fn configures_and_round_trips_the_standard_library_property() {
+        let mut rule = FoldConstants::default();
+        let mut properties = RuleProperties::new();
+        properties.insert(
+            "standard_library".to_owned(),
+            RulePropertyValue::String("luau".to_owned()),
+        );
+
+        rule.configure(properties).expect("should configure");
+
+        assert!(rule.standard_library.get_global("task").is_some());
+
+        let serialized = rule.serialize_to_properties();
+        assert!(matches!(
+            serialized.get("standard_library"),
+            Some(RulePropertyValue::String(name)) if name == "luau"
+        ));
+    }
+
+    #[test]
+    fn rejects_an_unknown_standard_library_name() {
+        let mut rule = FoldConstants::default();
+        let mut properties = RuleProperties::new();
+        properties.insert(
+            "standard_library".to_owned(),
+            RulePropertyValue::String("not-a-real-library.json".to_owned()),
+        );
+
+        let error = rule.configure(properties).unwrap_err();
+
+        assert!(matches!(error, RuleConfigurationError::StandardLibraryExpected(..)));
+    }
+}
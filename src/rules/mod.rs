@@ -1,8 +1,17 @@
 //! A module that contains the different rules that mutates a Lua block.
 
 mod empty_do;
+mod fold_constants;
+mod purity;
+mod remove_unused_locals;
+mod rename_variables;
+mod rule_context;
 
 pub use empty_do::*;
+pub use fold_constants::*;
+pub use remove_unused_locals::*;
+pub use rename_variables::*;
+pub use rule_context::{Diagnostic, RuleContext, Severity};
 
 use crate::nodes::Block;
 
@@ -15,11 +24,20 @@ use std::collections::HashMap;
 
 /// In order to be able to weakly-type the properties of any rule, this enum makes it possible to
 /// easily use serde to gather the value associated with a property.
+///
+/// `Usize` is tried before `Integer` so an ordinary non-negative value like `"threshold": 5` still
+/// deserializes as `Usize`, matching every existing rule property that expects one: with `Integer`
+/// first, it would claim every whole number and `Usize` would never be reached. With this order,
+/// `Integer` only receives negative numbers (`Usize` can't represent them).
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum RulePropertyValue {
-    String(String),
+    Boolean(bool),
     Usize(usize),
+    Integer(i64),
+    Float(f64),
+    StringList(Vec<String>),
+    String(String),
 }
 
 /// When implementing the configure method of the Rule trait, the method returns a result
@@ -33,6 +51,18 @@ pub enum RuleConfigurationError {
     /// When a property is associated with something else than an expected unsigned number. The
     /// string is the property name.
     UsizeExpected(String),
+    /// When a property is associated with something else than an expected boolean. The string is
+    /// the property name.
+    BoolExpected(String),
+    /// When a property is associated with something else than an expected floating point number.
+    /// The string is the property name.
+    FloatExpected(String),
+    /// When a property is associated with something else than an expected list of strings. The
+    /// string is the property name.
+    StringListExpected(String),
+    /// When a `standard_library` property names an unknown built-in library or a file that could
+    /// not be loaded. The first string is the property name, the second is the underlying error.
+    StandardLibraryExpected(String, String),
 }
 
 impl fmt::Display for RuleConfigurationError {
@@ -41,6 +71,14 @@ impl fmt::Display for RuleConfigurationError {
             Self::UnexpectedProperty(property) => write!(f, "unexpected field '{}'", property),
             Self::StringExpected(property) => write!(f, "string value expected for field '{}'", property),
             Self::UsizeExpected(property) => write!(f, "unsigned integer expected for field '{}'", property),
+            Self::BoolExpected(property) => write!(f, "boolean value expected for field '{}'", property),
+            Self::FloatExpected(property) => write!(f, "floating point number expected for field '{}'", property),
+            Self::StringListExpected(property) => write!(f, "list of strings expected for field '{}'", property),
+            Self::StandardLibraryExpected(property, message) => write!(
+                f,
+                "invalid standard library for field '{}': {}",
+                property, message
+            ),
         }
     }
 }
@@ -50,8 +88,9 @@ pub type RuleProperties = HashMap<String, RulePropertyValue>;
 /// Defines an interface that will be used to mutate blocks and how to serialize and deserialize
 /// the rule configuration.
 pub trait Rule {
-    /// This method should mutate the given block to apply the rule.
-    fn process(&self, block: &mut Block);
+    /// This method should mutate the given block to apply the rule, reporting what it did (or
+    /// refused to do) through the given context.
+    fn process(&self, block: &mut Block, context: &mut RuleContext);
     /// The rule deserializer will construct the default rule and then send the properties through
     /// this method to modify the behavior of the rule.
     fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError>;
@@ -68,6 +107,7 @@ pub trait Rule {
 pub fn get_default_rules() -> Vec<Box<dyn Rule>> {
     vec![
         Box::new(RemoveEmptyDo::default()),
+        Box::new(RenameVariables::default()),
     ]
 }
 
@@ -77,6 +117,9 @@ impl FromStr for Box<dyn Rule> {
     fn from_str(string: &str) -> Result<Self, Self::Err> {
         let rule = match string {
             REMOVE_EMPTY_DO_RULE_NAME => Box::new(RemoveEmptyDo::default()),
+            RENAME_VARIABLES_RULE_NAME => Box::new(RenameVariables::default()),
+            FOLD_CONSTANTS_RULE_NAME => Box::new(FoldConstants::default()),
+            REMOVE_UNUSED_LOCALS_RULE_NAME => Box::new(RemoveUnusedLocals::default()),
             _ => return Err(format!("invalid rule name: {}", string)),
         };
 
@@ -173,4 +216,18 @@ mod test {
 
         assert_json_snapshot!("default_rules", rules);
     }
+
+    #[test]
+    fn ordinary_whole_number_property_deserializes_as_usize() {
+        let value: RulePropertyValue = serde_json::from_str("5").expect("should parse");
+
+        assert!(matches!(value, RulePropertyValue::Usize(5)));
+    }
+
+    #[test]
+    fn negative_number_property_deserializes_as_integer() {
+        let value: RulePropertyValue = serde_json::from_str("-5").expect("should parse");
+
+        assert!(matches!(value, RulePropertyValue::Integer(-5)));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+//! Shared helpers for rules that need to know whether an expression can run side effects, used by
+//! both [`super::FoldConstants`] and [`super::RemoveUnusedLocals`].
+
+use crate::nodes::Expression;
+use crate::standard_library::StandardLibrary;
+
+/// Builds the dotted path of a call's callee (e.g. `math.floor`), if it is made only of
+/// identifiers and fields, so it can be looked up in a [`StandardLibrary`].
+pub fn dotted_path(expression: &Expression) -> Option<Vec<String>> {
+    match expression {
+        Expression::Identifier(name) => Some(vec![name.clone()]),
+        Expression::Field(field) => {
+            let mut path = dotted_path(field.get_object())?;
+            path.push(field.get_field().to_owned());
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
+/// Whether an expression contains no call other than to a pure [`StandardLibrary`] function.
+/// Operators, field accesses and index accesses count as side-effect free: metamethods they may
+/// invoke, and errors they may raise, are not taken into account.
+pub fn is_side_effect_free(expression: &Expression, library: &StandardLibrary) -> bool {
+    match expression {
+        Expression::True
+        | Expression::False
+        | Expression::Nil
+        | Expression::Number(_)
+        | Expression::String(_)
+        | Expression::Identifier(_)
+        | Expression::VariableArguments
+        | Expression::Function(_) => true,
+        Expression::Parenthese(inner) => is_side_effect_free(inner, library),
+        Expression::Unary(unary) => is_side_effect_free(unary.get_expression(), library),
+        Expression::Binary(binary) => {
+            is_side_effect_free(binary.get_left(), library)
+                && is_side_effect_free(binary.get_right(), library)
+        }
+        Expression::Table(table) => table
+            .get_entries()
+            .iter()
+            .all(|entry| is_side_effect_free(entry, library)),
+        Expression::Field(field) => is_side_effect_free(field.get_object(), library),
+        Expression::Index(index) => {
+            is_side_effect_free(index.get_object(), library)
+                && is_side_effect_free(index.get_index(), library)
+        }
+        Expression::Call(call) => {
+            dotted_path(call.get_prefix())
+                .map(|path| library.is_pure(&path))
+                .unwrap_or(false)
+                && call
+                    .get_arguments()
+                    .iter()
+                    .all(|argument| is_side_effect_free(argument, library))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dotted_path_resolves_a_bare_identifier() {
+        let path = dotted_path(&Expression::Identifier("math".to_owned()));
+
+        assert_eq!(path, Some(vec!["math".to_owned()]));
+    }
+
+    #[test]
+    fn dotted_path_rejects_a_non_identifier_expression() {
+        assert_eq!(dotted_path(&Expression::Number(1.0)), None);
+    }
+
+    #[test]
+    fn literals_and_identifiers_are_side_effect_free() {
+        let library = StandardLibrary::lua51();
+
+        assert!(is_side_effect_free(&Expression::Nil, &library));
+        assert!(is_side_effect_free(&Expression::Number(1.0), &library));
+        assert!(is_side_effect_free(&Expression::String("x".to_owned()), &library));
+        assert!(is_side_effect_free(&Expression::Identifier("x".to_owned()), &library));
+    }
+
+    #[test]
+    fn parenthesized_expression_inherits_inner_purity() {
+        let library = StandardLibrary::lua51();
+
+        let parenthesized = Expression::Parenthese(Box::new(Expression::Number(1.0)));
+
+        assert!(is_side_effect_free(&parenthesized, &library));
+    }
+}
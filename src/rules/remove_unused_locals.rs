@@ -0,0 +1,469 @@
+//! Drops `local` bindings that are never read, as long as dropping them cannot change behavior.
+
+use std::collections::HashMap;
+
+use crate::nodes::{Block, Expression, LastStatement, LocalAssignStatement, Statement};
+use crate::process::{NodeProcessor, ScopeVisitor};
+use crate::standard_library::StandardLibrary;
+
+use super::purity::is_side_effect_free;
+use super::{
+    Diagnostic, Rule, RuleConfigurationError, RuleContext, RuleProperties, RulePropertyValue,
+};
+
+pub const REMOVE_UNUSED_LOCALS_RULE_NAME: &str = "remove_unused_locals";
+
+/// A rule that removes `local` variables that are never referenced, as long as their initializer
+/// cannot have a side effect the program depends on.
+///
+/// To stay safe, a variable is only considered a removal candidate when its name is never used
+/// as an identifier anywhere else in the block: this can miss a dead variable that happens to
+/// share its name with a live one declared in another scope, but it never removes a variable
+/// that is actually read.
+#[derive(Debug)]
+pub struct RemoveUnusedLocals {
+    standard_library: StandardLibrary,
+    /// The raw `standard_library` property value this rule was configured with (a built-in name
+    /// or a file path), kept only so `serialize_to_properties` can round-trip it: a resolved
+    /// `StandardLibrary` no longer carries the selector it was built from.
+    standard_library_property: Option<String>,
+}
+
+impl Default for RemoveUnusedLocals {
+    fn default() -> Self {
+        Self {
+            standard_library: StandardLibrary::lua51(),
+            standard_library_property: None,
+        }
+    }
+}
+
+impl RemoveUnusedLocals {
+    pub fn with_standard_library(mut self, standard_library: StandardLibrary) -> Self {
+        self.standard_library = standard_library;
+        self
+    }
+}
+
+impl Rule for RemoveUnusedLocals {
+    fn process(&self, block: &mut Block, context: &mut RuleContext) {
+        let mut counter = UsageCounter::default();
+        ScopeVisitor::visit_block(block, &mut counter);
+
+        let removed_count = remove_unused_in_block(block, &counter.counts, &self.standard_library);
+
+        for _ in 0..removed_count {
+            context.record_mutation();
+        }
+
+        if removed_count > 0 {
+            context.report(Diagnostic::info(format!(
+                "removed {} unused local variable(s)",
+                removed_count
+            )));
+        }
+    }
+
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "standard_library" => match value {
+                    RulePropertyValue::String(name) => {
+                        self.standard_library = StandardLibrary::from_name_or_file(&name)
+                            .map_err(|err| {
+                                RuleConfigurationError::StandardLibraryExpected(
+                                    key.clone(),
+                                    err.to_string(),
+                                )
+                            })?;
+                        self.standard_library_property = Some(name);
+                    }
+                    _ => return Err(RuleConfigurationError::StringExpected(key)),
+                },
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        REMOVE_UNUSED_LOCALS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if let Some(name) = &self.standard_library_property {
+            properties.insert(
+                "standard_library".to_owned(),
+                RulePropertyValue::String(name.clone()),
+            );
+        }
+
+        properties
+    }
+}
+
+#[derive(Debug, Default)]
+struct UsageCounter {
+    counts: HashMap<String, usize>,
+}
+
+impl NodeProcessor for UsageCounter {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Identifier(name) = expression {
+            *self.counts.entry(name.clone()).or_insert(0) += 1;
+        }
+    }
+}
+
+fn remove_unused_in_block(
+    block: &mut Block,
+    counts: &HashMap<String, usize>,
+    library: &StandardLibrary,
+) -> usize {
+    let mut removed_count = 0;
+
+    block.mutate_statements().retain_mut(|statement| {
+        removed_count += visit_nested_blocks(statement, counts, library);
+
+        if let Statement::LocalAssign(local_assign) = statement {
+            removed_count += remove_unused_variables(local_assign, counts, library);
+
+            !local_assign.get_variables().is_empty()
+        } else {
+            true
+        }
+    });
+
+    if let Some(LastStatement::Return(expressions)) = block.mutate_last_statement() {
+        for expression in expressions {
+            removed_count += visit_expression_blocks(expression, counts, library);
+        }
+    }
+
+    removed_count
+}
+
+/// Removes unused variables from a `local` statement, respecting Lua's rule that the last value
+/// expression expands to fill any variable left without a dedicated value (e.g. `local _, v =
+/// ipairs(t)`, where both `_` and `v` are fed by the single call to `ipairs`).
+fn remove_unused_variables(
+    local_assign: &mut LocalAssignStatement,
+    counts: &HashMap<String, usize>,
+    library: &StandardLibrary,
+) -> usize {
+    let variables = local_assign.get_variables().clone();
+    let values = local_assign.mutate_values().clone();
+
+    let is_unused = |name: &str| counts.get(name).copied().unwrap_or(0) == 0;
+
+    let var_count = variables.len();
+    let val_count = values.len();
+    let expands = var_count > val_count && val_count >= 1;
+    // With expansion, the last value is shared by every variable from its own index onward.
+    // Without it, every variable has (at most) its own dedicated value at the same index.
+    let dedicated_end = if expands { val_count - 1 } else { var_count.min(val_count) };
+
+    let mut kept_variables = Vec::new();
+    let mut kept_values = Vec::new();
+    let mut removed_count = 0;
+
+    for index in 0..dedicated_end {
+        let name = &variables[index];
+        let value = &values[index];
+
+        if is_unused(name) && is_side_effect_free(value, library) {
+            removed_count += 1;
+        } else {
+            kept_variables.push(name.clone());
+            kept_values.push(value.clone());
+        }
+    }
+
+    if expands {
+        let shared_value = values[dedicated_end].clone();
+        let group = &variables[dedicated_end..var_count];
+
+        if group.iter().all(|name| is_unused(name)) && is_side_effect_free(&shared_value, library)
+        {
+            // Nothing reads any of the values the shared expression produces, and it cannot have
+            // a side effect, so the whole group (and the call itself) can be dropped.
+            removed_count += group.len();
+        } else {
+            // The call may still run (it could have a side effect, or an earlier variable in the
+            // group is used), so it must stay. Only variables at the very end of the group can be
+            // dropped without reindexing the ones before them.
+            let mut kept_len = group.len();
+
+            while kept_len > 0 && is_unused(&group[kept_len - 1]) {
+                kept_len -= 1;
+                removed_count += 1;
+            }
+
+            kept_variables.extend(group[..kept_len].iter().cloned());
+
+            if kept_len == 0 {
+                // Every variable fed by the shared value is unused, but the value itself must
+                // still run (it is not side-effect free), so keep a placeholder variable for it
+                // rather than dropping the whole `local` statement.
+                kept_variables.push("_".to_owned());
+            }
+
+            kept_values.push(shared_value);
+        }
+    } else if val_count > var_count {
+        // More values than variables: the extra ones are still evaluated (for their side
+        // effects) by Lua and then discarded, so they must be kept even though nothing reads them.
+        kept_values.extend(values[dedicated_end..val_count].iter().cloned());
+
+        if kept_variables.is_empty() {
+            // A `local` statement without any variable would be dropped along with those values,
+            // so keep a placeholder variable for them.
+            kept_variables.push("_".to_owned());
+        }
+    } else {
+        // More variables than values, including the no-initializer case (`local result`, where
+        // `val_count` is 0): every variable beyond `dedicated_end` has no value of its own, which
+        // is implicitly `nil` and therefore always side-effect free, so it can still be dropped
+        // when unused.
+        for name in &variables[dedicated_end..var_count] {
+            if is_unused(name) {
+                removed_count += 1;
+            } else {
+                kept_variables.push(name.clone());
+            }
+        }
+    }
+
+    *local_assign.mutate_variables() = kept_variables;
+    *local_assign.mutate_values() = kept_values;
+
+    removed_count
+}
+
+fn visit_nested_blocks(
+    statement: &mut Statement,
+    counts: &HashMap<String, usize>,
+    library: &StandardLibrary,
+) -> usize {
+    match statement {
+        Statement::Do(do_statement) => {
+            remove_unused_in_block(do_statement.mutate_block(), counts, library)
+        }
+        Statement::While(while_statement) => {
+            remove_unused_in_block(while_statement.mutate_block(), counts, library)
+        }
+        Statement::Repeat(repeat_statement) => {
+            remove_unused_in_block(repeat_statement.mutate_block(), counts, library)
+        }
+        Statement::NumericFor(numeric_for) => {
+            remove_unused_in_block(numeric_for.mutate_block(), counts, library)
+        }
+        Statement::GenericFor(generic_for) => {
+            remove_unused_in_block(generic_for.mutate_block(), counts, library)
+        }
+        Statement::LocalFunction(local_function) => {
+            remove_unused_in_block(local_function.mutate_block(), counts, library)
+        }
+        Statement::If(if_statement) => {
+            let mut removed_count = 0;
+
+            for branch in if_statement.mutate_branches() {
+                removed_count += remove_unused_in_block(branch.mutate_block(), counts, library);
+            }
+
+            if let Some(block) = if_statement.mutate_else_block() {
+                removed_count += remove_unused_in_block(block, counts, library);
+            }
+
+            removed_count
+        }
+        Statement::Assign(assign) => assign
+            .mutate_values()
+            .iter_mut()
+            .map(|value| visit_expression_blocks(value, counts, library))
+            .sum(),
+        Statement::LocalAssign(local_assign) => local_assign
+            .mutate_values()
+            .iter_mut()
+            .map(|value| visit_expression_blocks(value, counts, library))
+            .sum(),
+        Statement::CompoundAssign(compound_assign) => {
+            visit_expression_blocks(compound_assign.mutate_value(), counts, library)
+        }
+        Statement::Function(function_statement) => {
+            visit_expression_blocks(function_statement.mutate_function(), counts, library)
+        }
+        Statement::Call(call) => call
+            .mutate_arguments()
+            .iter_mut()
+            .map(|argument| visit_expression_blocks(argument, counts, library))
+            .sum(),
+    }
+}
+
+fn visit_expression_blocks(
+    expression: &mut Expression,
+    counts: &HashMap<String, usize>,
+    library: &StandardLibrary,
+) -> usize {
+    match expression {
+        Expression::Function(function) => {
+            remove_unused_in_block(function.mutate_block(), counts, library)
+        }
+        Expression::Binary(binary) => {
+            visit_expression_blocks(binary.mutate_left(), counts, library)
+                + visit_expression_blocks(binary.mutate_right(), counts, library)
+        }
+        Expression::Unary(unary) => {
+            visit_expression_blocks(unary.mutate_expression(), counts, library)
+        }
+        Expression::Parenthese(inner) => visit_expression_blocks(inner, counts, library),
+        Expression::Table(table) => table
+            .mutate_entries()
+            .iter_mut()
+            .map(|entry| visit_expression_blocks(entry, counts, library))
+            .sum(),
+        Expression::Call(call) => call
+            .mutate_arguments()
+            .iter_mut()
+            .map(|argument| visit_expression_blocks(argument, counts, library))
+            .sum(),
+        Expression::Field(field) => visit_expression_blocks(field.mutate_object(), counts, library),
+        Expression::Index(index) => {
+            visit_expression_blocks(index.mutate_object(), counts, library)
+                + visit_expression_blocks(index.mutate_index(), counts, library)
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::nodes::FunctionCall;
+
+    fn counts(used_names: &[&str]) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+
+        for name in used_names {
+            *counts.entry((*name).to_owned()).or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    fn impure_call(name: &str) -> Expression {
+        Expression::Call(FunctionCall::new(
+            Expression::Identifier(name.to_owned()),
+            Vec::new(),
+        ))
+    }
+
+    #[test]
+    fn drops_an_unused_local_with_a_pure_initializer() {
+        let mut local_assign =
+            LocalAssignStatement::new(vec!["a".to_owned()], vec![Expression::Number(1.0)]);
+
+        let removed = remove_unused_variables(&mut local_assign, &counts(&[]), &StandardLibrary::lua51());
+
+        assert_eq!(removed, 1);
+        assert!(local_assign.get_variables().is_empty());
+    }
+
+    #[test]
+    fn keeps_an_impure_shared_value_even_when_every_fed_variable_is_unused() {
+        // `local a, b = doSomething()` where `doSomething` is not a known pure library function:
+        // neither `a` nor `b` is ever read, but the call itself may still have a side effect and
+        // must keep running.
+        let mut local_assign = LocalAssignStatement::new(
+            vec!["a".to_owned(), "b".to_owned()],
+            vec![impure_call("doSomething")],
+        );
+
+        let removed = remove_unused_variables(&mut local_assign, &counts(&[]), &StandardLibrary::lua51());
+
+        assert_eq!(removed, 2);
+        assert_eq!(local_assign.mutate_values().len(), 1, "the impure call must survive");
+        assert!(
+            !local_assign.get_variables().is_empty(),
+            "a placeholder variable must be kept so the call statement is not dropped"
+        );
+    }
+
+    #[test]
+    fn keeps_an_uninitialized_local_that_is_read_later() {
+        // `local result` followed by later reads/writes of `result`: it has no value at all, so
+        // it must not be swept up just because it has nothing to pair with in the dedicated loop.
+        let mut local_assign = LocalAssignStatement::new(vec!["result".to_owned()], Vec::new());
+
+        let removed = remove_unused_variables(
+            &mut local_assign,
+            &counts(&["result", "result", "result"]),
+            &StandardLibrary::lua51(),
+        );
+
+        assert_eq!(removed, 0);
+        assert_eq!(local_assign.get_variables(), &vec!["result".to_owned()]);
+    }
+
+    #[test]
+    fn drops_an_uninitialized_local_that_is_never_read() {
+        let mut local_assign = LocalAssignStatement::new(vec!["result".to_owned()], Vec::new());
+
+        let removed = remove_unused_variables(&mut local_assign, &counts(&[]), &StandardLibrary::lua51());
+
+        assert_eq!(removed, 1);
+        assert!(local_assign.get_variables().is_empty());
+    }
+
+    #[test]
+    fn drops_the_whole_group_when_the_shared_value_is_pure_and_unused() {
+        let mut local_assign = LocalAssignStatement::new(
+            vec!["a".to_owned(), "b".to_owned()],
+            vec![Expression::Number(1.0)],
+        );
+
+        let removed = remove_unused_variables(&mut local_assign, &counts(&[]), &StandardLibrary::lua51());
+
+        assert_eq!(removed, 2);
+        assert!(local_assign.get_variables().is_empty());
+        assert!(local_assign.mutate_values().is_empty());
+    }
+
+    #[test]
+    fn configures_and_round_trips_the_standard_library_property() {
+        let mut rule = RemoveUnusedLocals::default();
+        let mut properties = RuleProperties::new();
+        properties.insert(
+            "standard_library".to_owned(),
+            RulePropertyValue::String("luau".to_owned()),
+        );
+
+        rule.configure(properties).expect("should configure");
+
+        assert!(rule.standard_library.get_global("task").is_some());
+
+        let serialized = rule.serialize_to_properties();
+        assert!(matches!(
+            serialized.get("standard_library"),
+            Some(RulePropertyValue::String(name)) if name == "luau"
+        ));
+    }
+
+    #[test]
+    fn rejects_an_unknown_standard_library_name() {
+        let mut rule = RemoveUnusedLocals::default();
+        let mut properties = RuleProperties::new();
+        properties.insert(
+            "standard_library".to_owned(),
+            RulePropertyValue::String("not-a-real-library.json".to_owned()),
+        );
+
+        let error = rule.configure(properties).unwrap_err();
+
+        assert!(matches!(error, RuleConfigurationError::StandardLibraryExpected(..)));
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+
+use crate::nodes::{
+    Expression, FunctionExpression, GenericForStatement, LocalAssignStatement,
+    LocalFunctionStatement, NumericForStatement,
+};
+use crate::process::NodeProcessor;
+
+/// First pass of the rename rule: walks the whole block to find every identifier that is never
+/// bound by a `local`, so the renamer can avoid ever reusing one of those names.
+#[derive(Debug, Default)]
+pub struct FreeNameCollector {
+    scopes: Vec<HashSet<String>>,
+    pending_bindings: Vec<String>,
+    pub free_names: HashSet<String>,
+}
+
+impl FreeNameCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_bound(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(name))
+    }
+}
+
+impl NodeProcessor for FreeNameCollector {
+    fn process_scope_start(&mut self, _block: &mut crate::nodes::Block) {
+        let mut scope = HashSet::new();
+        scope.extend(self.pending_bindings.drain(..));
+        self.scopes.push(scope);
+    }
+
+    fn process_scope_end(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn process_local_assign_statement(&mut self, statement: &mut LocalAssignStatement) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.extend(statement.get_variables().iter().cloned());
+        }
+    }
+
+    fn process_local_function_statement(&mut self, statement: &mut LocalFunctionStatement) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(statement.get_name().to_owned());
+        }
+    }
+
+    fn process_numeric_for_statement(&mut self, statement: &mut NumericForStatement) {
+        self.pending_bindings.push(statement.get_identifier().to_owned());
+    }
+
+    fn process_generic_for_statement(&mut self, statement: &mut GenericForStatement) {
+        self.pending_bindings
+            .extend(statement.get_identifiers().iter().cloned());
+    }
+
+    fn process_function_expression(&mut self, function: &mut FunctionExpression) {
+        self.pending_bindings
+            .extend(function.get_parameters().iter().cloned());
+    }
+
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Identifier(name) = expression {
+            if !self.is_bound(name) {
+                self.free_names.insert(name.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::nodes::LocalAssignStatement;
+
+    #[test]
+    fn does_not_consider_a_local_binding_free() {
+        let mut collector = FreeNameCollector::new();
+        collector.scopes.push(HashSet::new());
+
+        let mut local = LocalAssignStatement::from_variable("x");
+        collector.process_local_assign_statement(&mut local);
+
+        let mut usage = Expression::Identifier("x".to_owned());
+        collector.process_expression(&mut usage);
+
+        assert!(!collector.free_names.contains("x"));
+    }
+
+    #[test]
+    fn considers_an_unbound_identifier_free() {
+        let mut collector = FreeNameCollector::new();
+        collector.scopes.push(HashSet::new());
+
+        let mut usage = Expression::Identifier("print".to_owned());
+        collector.process_expression(&mut usage);
+
+        assert!(collector.free_names.contains("print"));
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::nodes::{
+    Block, Expression, FunctionExpression, GenericForStatement, LocalAssignStatement,
+    LocalFunctionStatement, NumericForStatement,
+};
+use crate::process::NodeProcessor;
+
+use super::name_generator::NameGenerator;
+
+/// Second pass of the rename rule: re-walks the block and actually rewrites every binding and
+/// reference, assigning the shortest name that does not collide with an enclosing scope, a
+/// preserved name or a free/global identifier.
+pub struct IdentifierRenamer<'a> {
+    preserved_names: &'a HashSet<String>,
+    free_names: &'a HashSet<String>,
+    scopes: Vec<HashMap<String, String>>,
+    pending_bindings: Vec<(String, String)>,
+    /// Number of local bindings whose name actually changed, used to report diagnostics.
+    pub renamed_count: usize,
+}
+
+impl<'a> IdentifierRenamer<'a> {
+    pub fn new(preserved_names: &'a HashSet<String>, free_names: &'a HashSet<String>) -> Self {
+        Self {
+            preserved_names,
+            free_names,
+            scopes: Vec::new(),
+            pending_bindings: Vec::new(),
+            renamed_count: 0,
+        }
+    }
+
+    fn is_reserved(&self, name: &str) -> bool {
+        self.free_names.contains(name)
+            || self.preserved_names.contains(name)
+            || self
+                .scopes
+                .iter()
+                .any(|scope| scope.values().any(|renamed| renamed == name))
+            || self
+                .pending_bindings
+                .iter()
+                .any(|(_, renamed)| renamed == name)
+    }
+
+    fn bind(&mut self, name: &str) -> String {
+        if self.preserved_names.contains(name) {
+            name.to_owned()
+        } else {
+            let mut generator = NameGenerator::new();
+            let new_name = generator.generate(|candidate| self.is_reserved(candidate));
+
+            if new_name != name {
+                self.renamed_count += 1;
+            }
+
+            new_name
+        }
+    }
+
+    fn bind_into_current_scope(&mut self, name: &str) -> String {
+        let new_name = self.bind(name);
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_owned(), new_name.clone());
+        }
+
+        new_name
+    }
+
+    fn resolve(&self, name: &str) -> Option<&String> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+    }
+}
+
+impl<'a> NodeProcessor for IdentifierRenamer<'a> {
+    fn process_scope_start(&mut self, _block: &mut Block) {
+        let mut scope = HashMap::new();
+        scope.extend(self.pending_bindings.drain(..));
+        self.scopes.push(scope);
+    }
+
+    fn process_scope_end(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn process_local_assign_statement(&mut self, statement: &mut LocalAssignStatement) {
+        let new_names: Vec<String> = statement
+            .get_variables()
+            .iter()
+            .map(|name| self.bind_into_current_scope(name))
+            .collect();
+
+        *statement.mutate_variables() = new_names;
+    }
+
+    fn process_local_function_statement(&mut self, statement: &mut LocalFunctionStatement) {
+        let name = statement.get_name().to_owned();
+        let new_name = self.bind_into_current_scope(&name);
+
+        *statement.mutate_name() = new_name;
+    }
+
+    fn process_numeric_for_statement(&mut self, statement: &mut NumericForStatement) {
+        let name = statement.get_identifier().to_owned();
+        let new_name = self.bind(&name);
+
+        *statement.mutate_identifier() = new_name.clone();
+        self.pending_bindings.push((name, new_name));
+    }
+
+    fn process_generic_for_statement(&mut self, statement: &mut GenericForStatement) {
+        // Each binding is registered right away, so the next identifier of the same statement
+        // cannot be given the same name.
+        let new_names: Vec<String> = statement
+            .get_identifiers()
+            .iter()
+            .map(|name| {
+                let new_name = self.bind(name);
+                self.pending_bindings.push((name.clone(), new_name.clone()));
+                new_name
+            })
+            .collect();
+
+        *statement.mutate_identifiers() = new_names;
+    }
+
+    fn process_function_expression(&mut self, function: &mut FunctionExpression) {
+        let new_names: Vec<String> = function
+            .get_parameters()
+            .iter()
+            .map(|name| {
+                let new_name = self.bind(name);
+                self.pending_bindings.push((name.clone(), new_name.clone()));
+                new_name
+            })
+            .collect();
+
+        *function.mutate_parameters() = new_names;
+    }
+
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Identifier(name) = expression {
+            if let Some(new_name) = self.resolve(name) {
+                *name = new_name.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::nodes::LocalAssignStatement;
+
+    #[test]
+    fn renames_successive_locals_to_the_shortest_free_names() {
+        let preserved = HashSet::new();
+        let free = HashSet::new();
+        let mut renamer = IdentifierRenamer::new(&preserved, &free);
+        renamer.scopes.push(HashMap::new());
+
+        let mut first = LocalAssignStatement::from_variable("alpha");
+        renamer.process_local_assign_statement(&mut first);
+        assert_eq!(first.get_variables(), &vec!["a".to_owned()]);
+
+        let mut second = LocalAssignStatement::from_variable("beta");
+        renamer.process_local_assign_statement(&mut second);
+        assert_eq!(second.get_variables(), &vec!["b".to_owned()]);
+
+        let mut usage = Expression::Identifier("alpha".to_owned());
+        renamer.process_expression(&mut usage);
+        assert_eq!(usage, Expression::Identifier("a".to_owned()));
+    }
+
+    #[test]
+    fn never_assigns_a_preserved_name_to_another_local() {
+        let preserved = HashSet::from(["a".to_owned()]);
+        let free = HashSet::new();
+        let mut renamer = IdentifierRenamer::new(&preserved, &free);
+        renamer.scopes.push(HashMap::new());
+
+        let mut local = LocalAssignStatement::from_variable("x");
+        renamer.process_local_assign_statement(&mut local);
+
+        assert_eq!(local.get_variables(), &vec!["b".to_owned()]);
+    }
+}
@@ -0,0 +1,131 @@
+//! Renames local variables to the shortest possible identifiers, scope by scope.
+
+mod free_name_collector;
+mod identifier_renamer;
+mod name_generator;
+
+use std::collections::HashSet;
+
+use crate::nodes::Block;
+use crate::process::ScopeVisitor;
+
+use self::free_name_collector::FreeNameCollector;
+use self::identifier_renamer::IdentifierRenamer;
+
+use super::{Diagnostic, Rule, RuleConfigurationError, RuleContext, RuleProperties, RulePropertyValue};
+
+pub const RENAME_VARIABLES_RULE_NAME: &str = "rename_variables";
+
+/// A rule that shortens every `local` variable name it can safely rename, without changing what
+/// the code does.
+#[derive(Debug, Default)]
+pub struct RenameVariables {
+    preserved_names: HashSet<String>,
+}
+
+impl Rule for RenameVariables {
+    fn process(&self, block: &mut Block, context: &mut RuleContext) {
+        let mut collector = FreeNameCollector::new();
+        ScopeVisitor::visit_block(block, &mut collector);
+
+        let mut renamer = IdentifierRenamer::new(&self.preserved_names, &collector.free_names);
+        ScopeVisitor::visit_block(block, &mut renamer);
+
+        for _ in 0..renamer.renamed_count {
+            context.record_mutation();
+        }
+
+        if renamer.renamed_count > 0 {
+            context.report(Diagnostic::info(format!(
+                "renamed {} local variable(s)",
+                renamer.renamed_count
+            )));
+        } else {
+            context.report(Diagnostic::info("no local variable needed renaming"));
+        }
+    }
+
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "preserve" => match value {
+                    RulePropertyValue::StringList(names) => {
+                        self.preserved_names.extend(names);
+                    }
+                    _ => return Err(RuleConfigurationError::StringListExpected(key)),
+                },
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        RENAME_VARIABLES_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if !self.preserved_names.is_empty() {
+            let mut names: Vec<String> = self.preserved_names.iter().cloned().collect();
+            names.sort();
+
+            properties.insert("preserve".to_owned(), RulePropertyValue::StringList(names));
+        }
+
+        properties
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::lua_generator::ToLua;
+    use crate::nodes::LocalAssignStatement;
+    use crate::process::NodeProcessor;
+
+    #[derive(Default)]
+    struct LocalNameCollector {
+        names: Vec<String>,
+    }
+
+    impl NodeProcessor for LocalNameCollector {
+        fn process_local_assign_statement(&mut self, statement: &mut LocalAssignStatement) {
+            self.names.extend(statement.get_variables().iter().cloned());
+        }
+    }
+
+    #[test]
+    fn renames_nested_scopes_without_reusing_a_still_live_outer_name() {
+        let mut block = Block::parse("local x = 1\ndo\nlocal x = 2\nprint(x)\nend\nreturn x")
+            .expect("fixture should parse");
+
+        let rule = RenameVariables::default();
+        let mut context = RuleContext::new();
+        rule.process(&mut block, &mut context);
+
+        let output = block.to_lua_string();
+        assert!(
+            !output.contains('x'),
+            "original name should not survive renaming: {}",
+            output
+        );
+
+        // Reparse the regenerated output and walk it with the same scope-aware visitor the rule
+        // itself uses, rather than asserting on the exact generated text: this exercises the real
+        // `Block`/`ScopeVisitor` pipeline end to end (a nested `do` block shadowing the outer
+        // name) without depending on incidental formatting choices.
+        let mut reparsed = Block::parse(&output).expect("regenerated code should still parse");
+        let mut collector = LocalNameCollector::default();
+        ScopeVisitor::visit_block(&mut reparsed, &mut collector);
+
+        assert_eq!(collector.names.len(), 2);
+        assert_ne!(
+            collector.names[0], collector.names[1],
+            "the inner scope must not reuse the outer scope's new name while it is still live"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+const LUA_KEYWORDS: &[&str] = &[
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+/// Produces an infinite sequence of short, valid Lua identifiers (`a`, `b`, ..., `z`, `aa`, `ab`,
+/// ...), skipping Lua keywords and any name rejected by the caller.
+#[derive(Debug, Default)]
+pub struct NameGenerator {
+    next_index: usize,
+}
+
+impl NameGenerator {
+    pub fn new() -> Self {
+        Self { next_index: 0 }
+    }
+
+    pub fn generate(&mut self, is_reserved: impl Fn(&str) -> bool) -> String {
+        loop {
+            let name = Self::name_from_index(self.next_index);
+            self.next_index += 1;
+
+            if !LUA_KEYWORDS.contains(&name.as_str()) && !is_reserved(&name) {
+                return name;
+            }
+        }
+    }
+
+    fn name_from_index(mut index: usize) -> String {
+        let mut letters = Vec::new();
+
+        loop {
+            let remainder = index % 26;
+            letters.push((b'a' + remainder as u8) as char);
+
+            if index < 26 {
+                break;
+            }
+
+            index = index / 26 - 1;
+        }
+
+        letters.into_iter().rev().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generates_single_letters_before_wrapping_to_double_letters() {
+        let mut generator = NameGenerator::new();
+        let names: Vec<String> = (0..28).map(|_| generator.generate(|_| false)).collect();
+
+        assert_eq!(names[0], "a");
+        assert_eq!(names[25], "z");
+        assert_eq!(names[26], "aa");
+        assert_eq!(names[27], "ab");
+    }
+
+    #[test]
+    fn skips_lua_keywords() {
+        let mut generator = NameGenerator::new();
+
+        // `do` would be the 119th name without the keyword check.
+        for _ in 0..200 {
+            let name = generator.generate(|_| false);
+            assert_ne!(name, "do");
+        }
+    }
+
+    #[test]
+    fn skips_reserved_names() {
+        let mut generator = NameGenerator::new();
+        let name = generator.generate(|name| name == "a");
+
+        assert_eq!(name, "b");
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+
+/// How important a [`Diagnostic`] is. Rules use this to distinguish a note about what they did
+/// from a warning about code they refused to touch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A message reported by a rule while it processes a block, describing what it did or why it
+/// refused to apply a transformation.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: String,
+    node_description: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new<S: Into<String>>(severity: Severity, message: S) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            node_description: None,
+        }
+    }
+
+    pub fn info<S: Into<String>>(message: S) -> Self {
+        Self::new(Severity::Info, message)
+    }
+
+    pub fn warning<S: Into<String>>(message: S) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    pub fn error<S: Into<String>>(message: S) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    /// Attaches a short description of the node the diagnostic is about, since this AST does not
+    /// carry source positions.
+    pub fn at<S: Into<String>>(mut self, node_description: S) -> Self {
+        self.node_description = Some(node_description.into());
+        self
+    }
+
+    pub fn get_severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn get_node_description(&self) -> Option<&str> {
+        self.node_description.as_deref()
+    }
+}
+
+/// Passed to every [`super::Rule::process`] call so a rule can report what it did (or why it
+/// didn't) without needing to return a value from `process` itself.
+#[derive(Debug, Default)]
+pub struct RuleContext {
+    diagnostics: Vec<Diagnostic>,
+    mutation_count: usize,
+}
+
+impl RuleContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Rules call this once for every mutation they apply to the block, so an overall count is
+    /// available from [`Self::get_mutation_count`] even when a rule does not emit a diagnostic for
+    /// each one.
+    pub fn record_mutation(&mut self) {
+        self.mutation_count += 1;
+    }
+
+    pub fn get_diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn get_mutation_count(&self) -> usize {
+        self.mutation_count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn records_one_mutation_per_call() {
+        let mut context = RuleContext::new();
+
+        context.record_mutation();
+        context.record_mutation();
+
+        assert_eq!(context.get_mutation_count(), 2);
+    }
+
+    #[test]
+    fn reports_diagnostics_in_order() {
+        let mut context = RuleContext::new();
+
+        context.report(Diagnostic::info("did something"));
+        context.report(Diagnostic::warning("refused to do something").at("local x"));
+
+        let diagnostics = context.get_diagnostics();
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].get_severity(), Severity::Info);
+        assert_eq!(diagnostics[1].get_severity(), Severity::Warning);
+        assert_eq!(diagnostics[1].get_node_description(), Some("local x"));
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// A single named argument of a library function, kept mostly for documentation purposes today
+/// but available for rules that want to check arity.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Parameter {
+    pub name: String,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// The definition of a plain (non-callable) global or field, such as `_VERSION` or `math.pi`.
+///
+/// `deny_unknown_fields` matters here: without it, any JSON/TOML object that is not a nested
+/// table (including a function definition with `pure`/`arguments` fields) would deserialize
+/// successfully as an empty, non-writable property, since every field here has a default.
+/// Rejecting unknown fields lets the untagged `LibraryValue` deserializer correctly fall through
+/// to `Function` instead.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PropertyDefinition {
+    /// Whether code is allowed to assign a new value to this global (e.g. `_G`).
+    #[serde(default)]
+    pub writable: bool,
+}
+
+/// The definition of a callable global or field, such as `math.floor` or `table.insert`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FunctionDefinition {
+    #[serde(default)]
+    pub arguments: Vec<Parameter>,
+    /// Whether the function is free of observable side effects and always returns the same
+    /// result for the same arguments, making it safe to fold or to drop if its result is unused.
+    #[serde(default)]
+    pub pure: bool,
+}
+
+/// A single entry of a [`super::StandardLibrary`]: a plain value, a callable function, or a
+/// nested table of further entries (e.g. the `math` table itself).
+///
+/// Variant order matters for the untagged deserializer: `Table` is tried first so a nested table
+/// of entries (whose fields are themselves objects, not scalars) is never mistaken for a single
+/// definition, and `Property` (with `deny_unknown_fields`) is tried before the permissive
+/// `Function` fallback so a function definition is never silently swallowed as an empty property.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum LibraryValue {
+    Table(HashMap<String, LibraryValue>),
+    Property(PropertyDefinition),
+    Function(FunctionDefinition),
+}
+
+impl LibraryValue {
+    pub fn is_pure(&self) -> bool {
+        matches!(self, Self::Function(function) if function.pure)
+    }
+
+    pub fn is_writable(&self) -> bool {
+        matches!(self, Self::Property(property) if property.writable)
+    }
+
+    pub fn get_field(&self, name: &str) -> Option<&LibraryValue> {
+        match self {
+            Self::Table(fields) => fields.get(name),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! A model of the globals available in a Lua environment, used by rules that need to know
+//! whether a global is side-effect free before folding a call to it or dropping code that calls
+//! it but never uses the result.
+
+mod definition;
+
+pub use definition::{FunctionDefinition, LibraryValue, Parameter, PropertyDefinition};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+const LUA51_STANDARD_LIBRARY: &str = include_str!("lua51.json");
+const LUAU_STANDARD_LIBRARY: &str = include_str!("luau.json");
+
+#[derive(Debug, Clone)]
+pub enum StandardLibraryError {
+    Io(PathBuf, String),
+    Parse(PathBuf, String),
+    UnknownBase(String),
+}
+
+impl fmt::Display for StandardLibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, message) => write!(f, "unable to read '{}': {}", path.display(), message),
+            Self::Parse(path, message) => write!(f, "unable to parse '{}': {}", path.display(), message),
+            Self::UnknownBase(name) => write!(f, "unknown standard library base '{}'", name),
+        }
+    }
+}
+
+/// A map of global names (and their dotted fields, such as `math.floor`) to their definition.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StandardLibrary {
+    /// The name of a built-in standard library to extend (`lua51` or `luau`).
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    globals: HashMap<String, LibraryValue>,
+}
+
+impl StandardLibrary {
+    pub fn lua51() -> Self {
+        serde_json::from_str(LUA51_STANDARD_LIBRARY)
+            .expect("built-in Lua 5.1 standard library should be valid")
+    }
+
+    pub fn luau() -> Self {
+        let mut library: Self = serde_json::from_str(LUAU_STANDARD_LIBRARY)
+            .expect("built-in Luau standard library should be valid");
+
+        library
+            .resolve_base()
+            .expect("built-in Luau standard library should have a valid base");
+
+        library
+    }
+
+    fn resolve_base(&mut self) -> Result<(), StandardLibraryError> {
+        if let Some(base) = self.base.take() {
+            let mut base_library = match base.as_str() {
+                "lua51" => Self::lua51(),
+                "luau" => Self::luau(),
+                other => return Err(StandardLibraryError::UnknownBase(other.to_owned())),
+            };
+
+            base_library.globals.extend(self.globals.drain());
+            self.globals = base_library.globals;
+        }
+
+        Ok(())
+    }
+
+    /// Loads a standard library definition from a JSON or TOML file, based on its extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, StandardLibraryError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|err| StandardLibraryError::Io(path.to_owned(), err.to_string()))?;
+
+        let mut library: Self = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => toml::from_str(&content)
+                .map_err(|err| StandardLibraryError::Parse(path.to_owned(), err.to_string()))?,
+            _ => serde_json::from_str(&content)
+                .map_err(|err| StandardLibraryError::Parse(path.to_owned(), err.to_string()))?,
+        };
+
+        library.resolve_base()?;
+
+        Ok(library)
+    }
+
+    /// Resolves a `standard_library` rule property: `"lua51"` and `"luau"` select the matching
+    /// built-in library, anything else is treated as a path and loaded with [`Self::from_file`].
+    pub fn from_name_or_file(value: &str) -> Result<Self, StandardLibraryError> {
+        match value {
+            "lua51" => Ok(Self::lua51()),
+            "luau" => Ok(Self::luau()),
+            path => Self::from_file(path),
+        }
+    }
+
+    pub fn get_global(&self, name: &str) -> Option<&LibraryValue> {
+        self.globals.get(name)
+    }
+
+    /// Resolves a dotted path like `["math", "floor"]` against the globals and their nested
+    /// fields.
+    pub fn get_path(&self, path: &[String]) -> Option<&LibraryValue> {
+        let (first, rest) = path.split_first()?;
+        let mut value = self.get_global(first)?;
+
+        for field in rest {
+            value = value.get_field(field)?;
+        }
+
+        Some(value)
+    }
+
+    pub fn is_pure(&self, path: &[String]) -> bool {
+        self.get_path(path).map(LibraryValue::is_pure).unwrap_or(false)
+    }
+
+    pub fn is_writable_global(&self, name: &str) -> bool {
+        self.get_global(name).map(LibraryValue::is_writable).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn path(segments: &[&str]) -> Vec<String> {
+        segments.iter().map(|segment| segment.to_string()).collect()
+    }
+
+    #[test]
+    fn lua51_resolves_nested_pure_functions() {
+        let library = StandardLibrary::lua51();
+
+        assert!(library.get_path(&path(&["math", "floor"])).is_some());
+        assert!(library.is_pure(&path(&["math", "floor"])));
+        assert!(library.is_pure(&path(&["string", "upper"])));
+    }
+
+    #[test]
+    fn lua51_does_not_consider_impure_functions_pure() {
+        let library = StandardLibrary::lua51();
+
+        assert!(!library.is_pure(&path(&["table", "insert"])));
+    }
+
+    #[test]
+    fn lua51_global_is_writable() {
+        let library = StandardLibrary::lua51();
+
+        assert!(library.is_writable_global("_G"));
+    }
+
+    #[test]
+    fn luau_extends_lua51_with_new_globals() {
+        let library = StandardLibrary::luau();
+
+        assert!(library.is_pure(&path(&["math", "floor"])));
+        assert!(library.get_global("task").is_some());
+    }
+
+    #[test]
+    fn from_name_or_file_resolves_built_in_names() {
+        assert!(StandardLibrary::from_name_or_file("lua51").is_ok());
+        assert!(StandardLibrary::from_name_or_file("luau")
+            .expect("luau should resolve")
+            .get_global("task")
+            .is_some());
+    }
+
+    #[test]
+    fn from_name_or_file_treats_unknown_names_as_a_file_path() {
+        let error = StandardLibrary::from_name_or_file("not-a-real-file.json").unwrap_err();
+
+        assert!(matches!(error, StandardLibraryError::Io(..)));
+    }
+}